@@ -0,0 +1,157 @@
+//! Rendering of errors against the source text they refer to.
+
+use alloc::string::{String, ToString};
+use core::fmt::{self, Write};
+
+/// A byte range into the source text.
+///
+/// Both ends must fall on character boundaries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Span { start, end }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// An error or warning with a code, the span it points at and an optional hint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: &'static str,
+    pub message: String,
+    pub span: Span,
+    pub hint: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn new(code: &'static str, message: impl Into<String>, span: Span) -> Self {
+        Diagnostic {
+            severity: Severity::Error,
+            code,
+            message: message.into(),
+            span,
+            hint: None,
+        }
+    }
+
+    pub fn warning(code: &'static str, message: impl Into<String>, span: Span) -> Self {
+        Diagnostic {
+            severity: Severity::Warning,
+            ..Diagnostic::new(code, message, span)
+        }
+    }
+
+    pub fn with_hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = Some(hint.into());
+        self
+    }
+
+    /// Renders the diagnostic with the offending line and a caret under the span.
+    ///
+    /// Spans covering several lines are underlined up to the end of their first line.
+    pub fn render(&self, source: &str) -> String {
+        let start = self.span.start.min(source.len());
+        let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = source[start..].find('\n').map_or(source.len(), |i| start + i);
+        let line_number = source[..line_start].matches('\n').count() + 1;
+        let line = source[line_start..line_end].trim_end_matches('\r');
+        let column = source[line_start..start].chars().count() + 1;
+
+        let end = self.span.end.max(start).min(line_start + line.len());
+        let width = source[start.min(end)..end].chars().count().max(1);
+
+        // Keep tabs so the caret lines up with the source line.
+        let padding: String = source[line_start..start]
+            .chars()
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        let gutter = " ".repeat(line_number.to_string().len());
+
+        let mut out = String::new();
+        let _ = writeln!(out, "{}[{}]: {}", self.severity, self.code, self.message);
+        let _ = writeln!(out, "{}--> {}:{}", gutter, line_number, column);
+        let _ = writeln!(out, "{} |", gutter);
+        let _ = writeln!(out, "{} | {}", line_number, line);
+        let _ = writeln!(out, "{} | {}{}", gutter, padding, "^".repeat(width));
+        if let Some(hint) = &self.hint {
+            let _ = writeln!(out, "{} = hint: {}", gutter, hint);
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_line_caret_and_hint() {
+        let source = "let a = 1;\nlet = 5;\n";
+        let diagnostic = Diagnostic::new("E0001", "expected identifier", Span::new(15, 16))
+            .with_hint("give the variable a name");
+
+        assert_eq!(
+            diagnostic.render(source),
+            "error[E0001]: expected identifier\n \
+             --> 2:5\n  \
+             |\n\
+             2 | let = 5;\n  \
+             |     ^\n  \
+             = hint: give the variable a name\n"
+        );
+    }
+
+    #[test]
+    fn renders_warning_severity() {
+        let source = "let unused = 1;";
+        let diagnostic = Diagnostic::warning("W0001", "unused variable", Span::new(4, 10));
+
+        assert!(diagnostic.render(source).starts_with("warning[W0001]: unused variable\n"));
+        assert!(diagnostic.render(source).ends_with("  |     ^^^^^^\n"));
+    }
+
+    #[test]
+    fn underlines_whole_span() {
+        let source = "print foo;";
+        let diagnostic = Diagnostic::new("E0002", "undefined variable", Span::new(6, 9));
+
+        assert!(diagnostic.render(source).ends_with("1 | print foo;\n  |       ^^^\n"));
+    }
+
+    #[test]
+    fn points_past_end_of_input() {
+        let source = "print (1";
+        let diagnostic = Diagnostic::new("E0003", "unclosed parenthesis", Span::new(8, 8));
+
+        assert!(diagnostic.render(source).contains(" --> 1:9\n"));
+        assert!(diagnostic.render(source).ends_with("  |         ^\n"));
+    }
+
+    #[test]
+    fn clamps_multi_line_span_to_first_line() {
+        let source = "{\n}";
+        let diagnostic = Diagnostic::new("E0004", "empty block", Span::new(0, 3));
+
+        assert!(diagnostic.render(source).ends_with("1 | {\n  | ^\n"));
+    }
+}
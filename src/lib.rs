@@ -1,3 +1,9 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod diagnostics;
+
 #[cfg(test)]
 mod tests {
     #[test]